@@ -4,8 +4,39 @@
 #
 # Environment variables:
 #   VERSION     - Install a specific version (e.g., VERSION=1.2.3)
+#   PRERELEASE  - Set to 1 to include prereleases, installing the newest release whether or not
+#                 it is a prerelease, instead of the latest stable release
 #   BINARIES    - Space-separated list of binaries to install (default: centy-daemon)
+#   PROFILE     - Named set of binaries: minimal, server or full (default: none)
 #   INSTALL_DIR - Custom installation directory (default: ~/.centy/bin)
+#   PORTABLE    - Set to 1 for a relocatable install in ./.centy with relative symlinks and no PATH changes
+#   CENTY_HOME  - Installation directory to use when HOME is not set
+#   QUIET       - Set to 1 to print only errors and the installed binary paths
+#   VERBOSE     - Set to 1 to also print URLs, cache paths and asset sizes
+#   MACOS_UNIVERSAL - Set to 0 to skip universal (Intel + Apple Silicon) macOS assets
+#   CENTY_ARCH  - Override the detected architecture (x86_64, aarch64 or armv7)
+#   BINARY_NAMES - Space-separated binary=file-name overrides for binaries inside archives;
+#                  use binary@<os>=file-name (e.g. centy-daemon@pc-windows-msvc=CentyDaemon.exe)
+#                  to apply one to a single OS
+#   BINARY_PATTERN - Extended regex for binary file names inside archives, {binary} is replaced
+#                    with the binary name (default: ^{binary}([-_.].*)?$, which also matches
+#                    versioned names like {binary}-v1.2.3; debug symbols (.debug, .dbg, .pdb,
+#                    .dSYM), checksums and signatures are never picked)
+#   ACTIVATION  - How binaries are linked into the bin directory: auto, symlink, hardlink or copy
+#   CACHE_DIR   - Shared download cache directory, e.g. /var/cache/centy (default: none);
+#                 only downloads verified by a published or pinned checksum are cached
+#   IP_VERSION  - Force IPv4 or IPv6 for all requests (4 or 6, default: both)
+#   RESOLVE     - Space-separated host:port:address DNS overrides (curl only)
+#   CONTINUE_ON_ERROR - Set to 0 to stop at the first failed binary and activate none (default: 1)
+#   VERIFY_CHECKSUMS - Set to 0 to skip verification against checksums published with a release
+#   CHECKSUM_PINS - File of "<binary> <version> <asset> <sha256>" lines; pinned versions must
+#                   match, and must list the asset downloaded for this platform
+#   CHECK       - Set to 1 to only report what would be installed, without changing anything
+#   REINSTALL   - Set to 1 to re-download a version that is already installed
+#   REPORT      - Write a JSON report of the installed versions, URLs and checksums to this file
+#   USER_AGENT  - Override the User-Agent sent with every request
+#   FORCE       - Set to 1 to re-download installed versions (implies REINSTALL) and to
+#                 overwrite a version whose binary is currently running
 
 set -e
 
@@ -18,7 +49,22 @@ NC='\033[0m' # No Color
 
 # Configuration
 GITHUB_ORG="centy-io"
-DEFAULT_INSTALL_DIR="${HOME}/.centy"
+# Some containers and service accounts have no HOME; fall back to CENTY_HOME,
+# then to the current directory (main warns about the latter)
+if [ -n "${HOME:-}" ]; then
+    DEFAULT_INSTALL_DIR="${HOME}/.centy"
+elif [ -n "${CENTY_HOME:-}" ]; then
+    DEFAULT_INSTALL_DIR="$CENTY_HOME"
+else
+    DEFAULT_INSTALL_DIR="$(pwd)/.centy"
+fi
+
+# Portable installs live next to where the installer is run and can be moved as a whole
+PORTABLE="${PORTABLE:-0}"
+if [ "$PORTABLE" = "1" ]; then
+    DEFAULT_INSTALL_DIR="$(pwd)/.centy"
+fi
+
 INSTALL_DIR="${INSTALL_DIR:-$DEFAULT_INSTALL_DIR}"
 BIN_DIR="${INSTALL_DIR}/bin"
 VERSIONS_DIR="${INSTALL_DIR}/versions"
@@ -26,7 +72,38 @@ VERSIONS_DIR="${INSTALL_DIR}/versions"
 # Default binaries to install (only those with releases)
 # Available: centy-daemon, centy-tui, tui-manager
 DEFAULT_BINARIES="centy-daemon centy-tui"
-BINARIES="${BINARIES:-$DEFAULT_BINARIES}"
+PROFILE="${PROFILE:-}"
+PRERELEASE="${PRERELEASE:-0}"
+BINARIES="${BINARIES:-}"
+CHECK="${CHECK:-0}"
+REINSTALL="${REINSTALL:-0}"
+FORCE="${FORCE:-0}"
+if [ "$FORCE" = "1" ]; then
+    REINSTALL=1
+fi
+CONTINUE_ON_ERROR="${CONTINUE_ON_ERROR:-1}"
+VERIFY_CHECKSUMS="${VERIFY_CHECKSUMS:-1}"
+CHECKSUM_PINS="${CHECKSUM_PINS:-}"
+QUIET="${QUIET:-0}"
+VERBOSE="${VERBOSE:-0}"
+REPORT="${REPORT:-}"
+
+# Optional machine-wide download cache shared across users. Installs stay
+# per-user; only the downloaded release assets are shared.
+CACHE_DIR="${CACHE_DIR:-}"
+
+# Prefer universal macOS assets, which run on both Intel and Apple Silicon
+MACOS_UNIVERSAL="${MACOS_UNIVERSAL:-1}"
+CENTY_ARCH="${CENTY_ARCH:-}"
+ACTIVATION="${ACTIVATION:-auto}"
+BINARY_NAMES="${BINARY_NAMES:-}"
+DEFAULT_BINARY_PATTERN='^{binary}([-_.].*)?$'
+BINARY_PATTERN="${BINARY_PATTERN:-$DEFAULT_BINARY_PATTERN}"
+
+# Network overrides for networks with broken IPv6 or split-horizon DNS
+IP_VERSION="${IP_VERSION:-}"
+RESOLVE="${RESOLVE:-}"
+USER_AGENT="${USER_AGENT:-}"
 
 # Print functions
 info() {
@@ -45,6 +122,13 @@ error() {
     printf "${RED}error${NC}: %s\n" "$1" >&2
 }
 
+# Debug output goes to stderr so it is safe inside command substitutions
+debug() {
+    if [ "$VERBOSE" = "1" ]; then
+        printf "${BLUE}debug${NC}: %s\n" "$1" >&2
+    fi
+}
+
 # Detect operating system (new format: apple-darwin, unknown-linux-gnu)
 detect_os() {
     case "$(uname -s)" in
@@ -85,23 +169,61 @@ detect_os_legacy() {
 
 # Detect architecture
 detect_arch() {
-    case "$(uname -m)" in
+    machine="${CENTY_ARCH:-$(uname -m)}"
+
+    # An x86_64 shell under Rosetta on Apple Silicon should still get native assets
+    if [ -z "$CENTY_ARCH" ] && [ "$machine" = "x86_64" ] && [ "$(uname -s)" = "Darwin" ] \
+        && [ "$(sysctl -n sysctl.proc_translated 2>/dev/null)" = "1" ]; then
+        machine="arm64"
+    fi
+
+    case "$machine" in
         x86_64|amd64)
             echo "x86_64"
             ;;
         aarch64|arm64)
             echo "aarch64"
             ;;
-        armv7l)
+        armv7l|armv7)
             echo "armv7"
             ;;
         *)
-            error "Unsupported architecture: $(uname -m)"
+            error "Unsupported architecture: $machine"
             exit 1
             ;;
     esac
 }
 
+# Check if running under Windows Subsystem for Linux
+is_wsl() {
+    [ -e /proc/sys/fs/binfmt_misc/WSLInterop ] && return 0
+    grep -qi microsoft /proc/version 2>/dev/null
+}
+
+# Warn about WSL setups that mix Windows and Linux installs
+check_wsl() {
+    is_wsl || return 0
+
+    info "Detected WSL: installing Linux binaries"
+
+    case "$INSTALL_DIR" in
+        /mnt/[a-z]/*)
+            warn "INSTALL_DIR is on the Windows filesystem (${INSTALL_DIR})"
+            warn "A Windows install of centy may share this directory; prefer a Linux path such as ~/.centy"
+            ;;
+    esac
+
+    # Windows PATH entries are inherited by WSL and may shadow the Linux binaries
+    for name in $BINARIES; do
+        existing=$(command -v "$name" 2>/dev/null || true)
+        case "$existing" in
+            /mnt/[a-z]/*)
+                warn "$name currently resolves to a Windows install: $existing"
+                ;;
+        esac
+    done
+}
+
 # Get archive extension based on OS
 get_archive_ext() {
     os="$1"
@@ -115,8 +237,52 @@ get_archive_ext() {
     esac
 }
 
+# Get the binaries for a named installation profile
+get_profile_binaries() {
+    case "$1" in
+        minimal)
+            echo "centy-tui"
+            ;;
+        server)
+            echo "centy-daemon"
+            ;;
+        full)
+            # tui-manager joins once it publishes releases
+            echo "centy-daemon centy-tui"
+            ;;
+        *)
+            error "Unknown profile: $1 (available: minimal, server, full)"
+            return 1
+            ;;
+    esac
+}
+
+# Resolve which binaries to install from BINARIES, PROFILE or the defaults
+resolve_binaries() {
+    if [ -n "$BINARIES" ]; then
+        if [ -n "$PROFILE" ]; then
+            warn "Both BINARIES and PROFILE are set, ignoring PROFILE=${PROFILE}"
+            PROFILE=""
+        fi
+    elif [ -n "$PROFILE" ]; then
+        BINARIES=$(get_profile_binaries "$PROFILE") || exit 1
+    else
+        BINARIES="$DEFAULT_BINARIES"
+    fi
+}
+
 # Check for required commands
 check_requirements() {
+    # Fail early on unsupported platforms (the detect functions exit on error)
+    detect_os >/dev/null
+    detect_arch >/dev/null
+
+    # Windows only runs binaries that keep their .exe extension
+    EXE_SUFFIX=""
+    if [ "$(detect_os)" = "pc-windows-msvc" ]; then
+        EXE_SUFFIX=".exe"
+    fi
+
     if command -v curl >/dev/null 2>&1; then
         DOWNLOAD_CMD="curl"
     elif command -v wget >/dev/null 2>&1; then
@@ -126,6 +292,42 @@ check_requirements() {
         exit 1
     fi
 
+    case "$ACTIVATION" in
+        auto|symlink|hardlink|copy) ;;
+        *)
+            error "ACTIVATION must be auto, symlink, hardlink or copy (got: $ACTIVATION)"
+            exit 1
+            ;;
+    esac
+
+    # Build extra download options from network overrides
+    DOWNLOAD_OPTS=""
+    case "$IP_VERSION" in
+        "") ;;
+        4|6)
+            DOWNLOAD_OPTS="-${IP_VERSION}"
+            ;;
+        *)
+            error "IP_VERSION must be 4 or 6 (got: $IP_VERSION)"
+            exit 1
+            ;;
+    esac
+
+    if [ -n "$RESOLVE" ]; then
+        if [ "$DOWNLOAD_CMD" = "curl" ]; then
+            for entry in $RESOLVE; do
+                DOWNLOAD_OPTS="${DOWNLOAD_OPTS} --resolve ${entry}"
+            done
+        else
+            warn "RESOLVE is only supported with curl, ignoring it"
+        fi
+    fi
+
+    # Identify the installer and platform to GitHub and mirror operators
+    if [ -z "$USER_AGENT" ]; then
+        USER_AGENT="centy-installer/sh ($(detect_arch)-$(detect_os))"
+    fi
+
     # Check for tar (needed for extraction)
     if ! command -v tar >/dev/null 2>&1; then
         error "tar is required for extraction"
@@ -139,9 +341,9 @@ download() {
     output="$2"
 
     if [ "$DOWNLOAD_CMD" = "curl" ]; then
-        curl -fsSL "$url" -o "$output"
+        curl $DOWNLOAD_OPTS -A "$USER_AGENT" -fsSL "$url" -o "$output"
     else
-        wget -q "$url" -O "$output"
+        wget $DOWNLOAD_OPTS -U "$USER_AGENT" -q "$url" -O "$output"
     fi
 }
 
@@ -150,22 +352,112 @@ fetch_json() {
     url="$1"
 
     if [ "$DOWNLOAD_CMD" = "curl" ]; then
-        curl -fsSL "$url"
+        curl $DOWNLOAD_OPTS -A "$USER_AGENT" -fsSL "$url"
     else
-        wget -q "$url" -O -
+        wget $DOWNLOAD_OPTS -U "$USER_AGENT" -q "$url" -O -
     fi
 }
 
+# Get the HTTP status code of a URL without following redirects (000 if unreachable)
+http_status() {
+    url="$1"
+
+    if [ "$DOWNLOAD_CMD" = "curl" ]; then
+        code=$(curl $DOWNLOAD_OPTS -A "$USER_AGENT" -s -o /dev/null -w '%{http_code}' "$url" 2>/dev/null) || true
+        echo "${code:-000}"
+    else
+        wget $DOWNLOAD_OPTS -U "$USER_AGENT" --spider -S --max-redirect=0 "$url" 2>&1 \
+            | awk '$1 ~ /^HTTP\// { code = $2 } END { print (code == "" ? "000" : code) }'
+    fi
+}
+
+# Get the Location header of a redirect response, if any
+http_location() {
+    url="$1"
+
+    if [ "$DOWNLOAD_CMD" = "curl" ]; then
+        curl $DOWNLOAD_OPTS -A "$USER_AGENT" -s -o /dev/null -w '%{redirect_url}' "$url" 2>/dev/null || true
+    else
+        wget $DOWNLOAD_OPTS -U "$USER_AGENT" --spider -S --max-redirect=0 "$url" 2>&1 \
+            | awk 'tolower($1) == "location:" { location = $2 } END { print location }' | tr -d '\r'
+    fi
+}
+
+# Get the new owner/name of a moved repository from the API redirect; GitHub
+# redirects renamed repositories to /repositories/<id>, which is looked up
+moved_repository() {
+    location=$(http_location "$1")
+    case "$location" in
+        */repos/*)
+            echo "$location" | sed -n 's|.*/repos/\([^/]*/[^/?]*\).*|\1|p'
+            ;;
+        */repositories/*)
+            repo_id=$(echo "$location" | sed -n 's|.*/repositories/\([0-9]*\).*|\1|p')
+            if [ -n "$repo_id" ]; then
+                fetch_json "https://api.github.com/repositories/${repo_id}" 2>/dev/null \
+                    | sed -n 's/.*"full_name"[[:space:]]*:[[:space:]]*"\([^"]*\)".*/\1/p' | head -1
+            fi
+            ;;
+    esac
+}
+
+# Warn when release info was served through a redirect from a moved repository
+check_repository_moved() {
+    repo="$1"
+    response="$2"
+
+    current=$(echo "$response" | tr ',' '\n' \
+        | sed -n 's|.*"html_url"[[:space:]]*:[[:space:]]*"https://github.com/\([^/"]*/[^/"]*\)/releases/.*|\1|p' | head -1)
+    if [ -n "$current" ] && [ "$(echo "$current" | tr 'A-Z' 'a-z')" != "$(echo "${GITHUB_ORG}/${repo}" | tr 'A-Z' 'a-z')" ]; then
+        warn "Repository ${GITHUB_ORG}/${repo} has moved to ${current}; update the installer to use the new name" >&2
+    fi
+}
+
+# Explain why a GitHub API request for a repository failed
+report_api_failure() {
+    repo="$1"
+    api_url="$2"
+
+    status=$(http_status "$api_url")
+    case "$status" in
+        404)
+            error "Repository ${GITHUB_ORG}/${repo} was not found or has no releases"
+            ;;
+        401|403)
+            error "Access to ${GITHUB_ORG}/${repo} was denied (HTTP $status)"
+            error "The repository may be private, or the GitHub API rate limit was exceeded; try again later"
+            ;;
+        301|302|307|308)
+            new_repo=$(moved_repository "$api_url")
+            if [ -n "$new_repo" ]; then
+                error "Repository ${GITHUB_ORG}/${repo} has moved to ${new_repo} (HTTP $status) and the redirect could not be followed"
+                error "Update the installer to use ${new_repo}"
+            else
+                error "Repository ${GITHUB_ORG}/${repo} has moved (HTTP $status) and the redirect could not be followed"
+                error "Check https://github.com/${GITHUB_ORG}/${repo} for its new location"
+            fi
+            ;;
+        000)
+            error "Could not reach api.github.com to fetch release info for $repo (check your network or proxy)"
+            ;;
+        *)
+            error "Failed to fetch release info for $repo (HTTP $status)"
+            ;;
+    esac
+}
+
 # Get latest version from GitHub API
 get_latest_version() {
     repo="$1"
     api_url="https://api.github.com/repos/${GITHUB_ORG}/${repo}/releases/latest"
+    debug "  Fetching: $api_url"
 
     # Fetch release info and extract tag_name
     response=$(fetch_json "$api_url" 2>/dev/null) || {
-        error "Failed to fetch release info for $repo (repo may not exist or have no releases)"
+        report_api_failure "$repo" "$api_url"
         return 1
     }
+    check_repository_moved "$repo" "$response"
 
     # Extract tag_name using sed (POSIX compatible)
     version=$(echo "$response" | sed -n 's/.*"tag_name"[[:space:]]*:[[:space:]]*"\([^"]*\)".*/\1/p' | head -1)
@@ -179,6 +471,33 @@ get_latest_version() {
     echo "$version"
 }
 
+# Get the newest release version from GitHub API, prereleases included
+get_newest_release() {
+    repo="$1"
+    api_url="https://api.github.com/repos/${GITHUB_ORG}/${repo}/releases?per_page=30"
+    debug "  Fetching: $api_url"
+
+    response=$(fetch_json "$api_url" 2>/dev/null) || {
+        report_api_failure "$repo" "$api_url"
+        return 1
+    }
+    check_repository_moved "$repo" "$response"
+
+    # Releases are listed newest first; pick the first one that is not a draft,
+    # so a stable release newer than the latest prerelease wins
+    version=$(echo "$response" | tr ',' '\n' | awk -F'"' '
+        /"tag_name"[[:space:]]*:/ { tag = $4 }
+        /"draft"[[:space:]]*:[[:space:]]*false/ { if (tag != "") { print tag; exit } }
+    ')
+
+    if [ -z "$version" ]; then
+        error "Could not find a release for $repo"
+        return 1
+    fi
+
+    echo "$version"
+}
+
 # Extract archive
 extract_archive() {
     archive="$1"
@@ -199,28 +518,671 @@ extract_archive() {
     esac
 }
 
+# List the file names a binary may have inside an archive, in order of preference
+binary_file_names() {
+    binary="$1"
+    os="$2"
+
+    # BINARY_NAMES overrides, e.g. "centy-daemon=centyd" for every platform or
+    # "centy-daemon@pc-windows-msvc=CentyDaemon.exe" for one
+    for mapping in $BINARY_NAMES; do
+        case "$mapping" in
+            "${binary}="*|"${binary}@${os}="*)
+                printf "%s " "${mapping#*=}"
+                ;;
+        esac
+    done
+
+    printf "%s" "$binary"
+    if [ "$os" = "pc-windows-msvc" ]; then
+        printf " %s.exe" "$binary"
+    fi
+    echo ""
+}
+
+# Find a binary in an extracted archive, preferring the top level
+find_binary_in_dir() {
+    dir="$1"
+    binary="$2"
+    os="$3"
+
+    names=$(binary_file_names "$binary" "$os")
+    for name in $names; do
+        if [ -f "${dir}/${name}" ]; then
+            echo "${dir}/${name}"
+            return 0
+        fi
+    done
+    for name in $names; do
+        found=$(find "$dir" -name "$name" -type f ! -path '*.dSYM/*' 2>/dev/null | head -1)
+        if [ -n "$found" ]; then
+            echo "$found"
+            return 0
+        fi
+    done
+
+    # Fall back to BINARY_PATTERN for names that embed a version or target,
+    # accepting only files that look like executables. Split debug symbols are
+    # executables too, so they and checksum or signature files are skipped.
+    pattern=$(echo "$BINARY_PATTERN" | sed "s/{binary}/${binary}/g")
+    find "$dir" -type f ! -path '*.dSYM/*' 2>/dev/null | sort | while read -r candidate; do
+        case "$candidate" in
+            *.debug|*.dbg|*.pdb|*.sha256|*.sig|*.asc)
+                continue
+                ;;
+        esac
+        if basename "$candidate" | grep -Eq "$pattern" && [ -n "$(binary_arch "$candidate")" ]; then
+            echo "$candidate"
+            break
+        fi
+    done
+}
+
 # Try to download from a URL, return 0 on success, 1 on failure
 try_download() {
     url="$1"
     output="$2"
 
     if [ "$DOWNLOAD_CMD" = "curl" ]; then
-        curl -fsSL "$url" -o "$output" 2>/dev/null
+        curl $DOWNLOAD_OPTS -A "$USER_AGENT" -fsSL "$url" -o "$output" 2>/dev/null
+    else
+        wget $DOWNLOAD_OPTS -U "$USER_AGENT" -q "$url" -O "$output" 2>/dev/null
+    fi
+}
+
+# Get the size in bytes of a remote file, following redirects (empty if unknown)
+remote_size() {
+    url="$1"
+
+    if [ "$DOWNLOAD_CMD" = "curl" ]; then
+        headers=$(curl $DOWNLOAD_OPTS -A "$USER_AGENT" -fsSIL "$url" 2>/dev/null) || return 1
+    else
+        headers=$(wget $DOWNLOAD_OPTS -U "$USER_AGENT" --spider -S "$url" 2>&1) || return 1
+    fi
+
+    # The last Content-Length belongs to the final response after redirects
+    echo "$headers" | tr -d '\r' | awk 'tolower($1) == "content-length:" { len = $2 } END { print len }'
+}
+
+# Get free space in KiB on the filesystem holding a directory
+free_space_kb() {
+    df -Pk "$1" 2>/dev/null | awk 'NR == 2 { print $4 }'
+}
+
+# Fail early if there is not enough disk space for an asset of the given size
+check_disk_space() {
+    size="$1"
+    is_archive_asset="$2"
+
+    # Extracted archives are estimated at three times their compressed size
+    if [ "$is_archive_asset" = "true" ]; then
+        extracted_kb=$((size * 3 / 1024 + 1))
+        tmp_needed_kb=$((size / 1024 + 1 + extracted_kb))
     else
-        wget -q "$url" -O "$output" 2>/dev/null
+        extracted_kb=$((size / 1024 + 1))
+        tmp_needed_kb=$extracted_kb
+    fi
+
+    tmp_free_kb=$(free_space_kb "$tmp_dir")
+    if [ -n "$tmp_free_kb" ] && [ "$tmp_free_kb" -lt "$tmp_needed_kb" ]; then
+        error "Not enough disk space in $tmp_dir: need ${tmp_needed_kb} KiB, have ${tmp_free_kb} KiB"
+        return 1
+    fi
+
+    install_free_kb=$(free_space_kb "$VERSIONS_DIR")
+    if [ -n "$install_free_kb" ] && [ "$install_free_kb" -lt "$extracted_kb" ]; then
+        error "Not enough disk space in $VERSIONS_DIR: need ${extracted_kb} KiB, have ${install_free_kb} KiB"
+        return 1
     fi
 }
 
+# Check the asset size against free disk space before downloading it
+preflight_download() {
+    url="$1"
+
+    size=$(remote_size "$url") || return 0
+    case "$size" in
+        ''|*[!0-9]*)
+            debug "  Size: unknown"
+            return 0
+            ;;
+    esac
+    debug "  Size: $size bytes"
+
+    case "$url" in
+        *.tar.gz|*.zip)
+            check_disk_space "$size" true
+            ;;
+        *)
+            check_disk_space "$size" false
+            ;;
+    esac
+}
+
+# Download a file, going through the shared cache when CACHE_DIR is set.
+# Returns 1 if the file could not be downloaded and 2 if there is not enough disk space.
+# Fresh downloads are only added to the cache by store_cached_download once verified.
+cached_download() {
+    url="$1"
+    output="$2"
+    cache_file=""
+    cache_hit=false
+
+    debug "  URL: $url"
+
+    if [ -n "$CACHE_DIR" ]; then
+        # Cache key: <org>/<repo>/<tag>/<asset>
+        cache_file="${CACHE_DIR}/$(echo "$url" | sed 's|^https://github.com/||; s|/releases/download/|/|')"
+
+        if [ -f "$cache_file" ]; then
+            info "  Using cached download: $cache_file"
+            cp "$cache_file" "$output"
+            cache_hit=true
+            last_download_url="$url"
+            return 0
+        fi
+    fi
+
+    preflight_download "$url" || return 2
+    try_download "$url" "$output" || return 1
+    last_download_url="$url"
+}
+
+# Add a verified download to the shared cache
+store_cached_download() {
+    file="$1"
+
+    # Unverified downloads would never be served from the cache, so skip them
+    if [ -z "$cache_file" ] || [ "$cache_hit" = "true" ] || [ "$checksum_verified" != "true" ]; then
+        return 0
+    fi
+
+    # Publish via rename so concurrent installers never read a partial file
+    cache_tmp="${cache_file}.tmp.$$"
+    if (umask 002 && mkdir -p "$(dirname "$cache_file")") 2>/dev/null \
+        && cp "$file" "$cache_tmp" 2>/dev/null \
+        && chmod 644 "$cache_tmp" \
+        && mv -f "$cache_tmp" "$cache_file"; then
+        info "  Cached: $cache_file"
+    else
+        rm -f "$cache_tmp" 2>/dev/null
+        warn "Could not write to cache directory ${CACHE_DIR}"
+    fi
+    cache_tmp=""
+}
+
+# Drop a cached download that failed verification so no one installs it again
+discard_cached_download() {
+    if [ "$cache_hit" != "true" ]; then
+        return 0
+    fi
+
+    if rm -f "$cache_file" 2>/dev/null && [ ! -e "$cache_file" ]; then
+        warn "Removed bad cache entry: $cache_file"
+    else
+        warn "Could not remove bad cache entry: $cache_file"
+    fi
+}
+
+# Compute the SHA-256 of a file
+sha256_of() {
+    if command -v sha256sum >/dev/null 2>&1; then
+        sha256sum "$1" | awk '{print $1}'
+    elif command -v shasum >/dev/null 2>&1; then
+        shasum -a 256 "$1" | awk '{print $1}'
+    else
+        return 1
+    fi
+}
+
+# Extract the SHA-256 for an asset from a checksum file. Understands
+# sha256sum style ("<hash>  <file>" or "<hash> *<file>"), BSD style
+# ("SHA256 (<file>) = <hash>") and bare "<hash>" per-asset files. Only the
+# file name is compared, so entries like "dist/<file>" match too.
+parse_checksum() {
+    checksum_file="$1"
+    asset_name="$2"
+
+    tr -d '\r' < "$checksum_file" | awk -v name="$asset_name" '
+        /^SHA256 \(/ {
+            file = $0
+            sub(/^SHA256 \(/, "", file)
+            sub(/\) = [0-9a-fA-F]+$/, "", file)
+            sub(/^.*\//, "", file)
+            if (file == name) { print tolower($NF); exit }
+            next
+        }
+        NF == 1 && $1 ~ /^[0-9a-fA-F]+$/ && length($1) == 64 { bare = $1; next }
+        NF >= 2 {
+            file = $2
+            sub(/^\*/, "", file)
+            sub(/^.*\//, "", file)
+            if (file == name) { print tolower($1); exit }
+        }
+        END { if (bare != "" && NR == 1) print tolower(bare) }
+    '
+}
+
+# Look up the published SHA-256 for a release asset: per-asset checksum
+# files first, then release-wide lists; prints nothing if none is published.
+# The checksum list is downloaded to the given file, which the caller tracks.
+published_checksum() {
+    asset_url="$1"
+    checksum_file="$2"
+
+    asset_name="${asset_url##*/}"
+    release_url="${asset_url%/*}"
+
+    expected=""
+    for candidate in "${asset_url}.sha256" "${release_url}/checksums.txt" "${release_url}/SHA256SUMS"; do
+        if try_download "$candidate" "$checksum_file"; then
+            debug "  Checksums: $candidate"
+            expected=$(parse_checksum "$checksum_file" "$asset_name")
+            if [ -n "$expected" ]; then
+                break
+            fi
+        fi
+    done
+    rm -f "$checksum_file"
+
+    echo "$expected"
+}
+
+# Verify a downloaded asset against the checksum published with the release
+verify_published_checksum() {
+    asset_url="$1"
+    file="$2"
+
+    if [ "$VERIFY_CHECKSUMS" = "0" ]; then
+        return 0
+    fi
+
+    asset_name="${asset_url##*/}"
+    expected=$(published_checksum "$asset_url" "${tmp_dir}/checksum.txt")
+
+    if [ -z "$expected" ]; then
+        debug "  No published checksum found for $asset_name"
+        return 0
+    fi
+
+    actual=$(sha256_of "$file") || {
+        warn "sha256sum or shasum not found, skipping checksum verification"
+        return 0
+    }
+
+    if [ "$actual" != "$expected" ]; then
+        error "Checksum mismatch for $asset_name"
+        error "  Expected: $expected"
+        error "  Actual:   $actual"
+        return 1
+    fi
+
+    checksum_verified=true
+    info "  Checksum verified"
+}
+
+# Look up the CHECKSUM_PINS entry for a release asset; prints the pinned
+# checksum, "-" if the version is pinned for other assets only, or nothing
+pinned_checksum() {
+    awk -v b="$1" -v v="$2" -v a="$3" '
+        $1 ~ /^#/ { next }
+        $1 == b && ($2 == v || $2 == "v" v) {
+            pinned = 1
+            if ($3 == a) { print tolower($4); found = 1; exit }
+        }
+        END { if (pinned && !found) print "-" }
+    ' "$CHECKSUM_PINS"
+}
+
+# Verify a downloaded asset against CHECKSUM_PINS if its version is pinned
+verify_pinned_checksum() {
+    pin_binary="$1"
+    pin_version="$2"
+    file="$3"
+    pin_asset="$4"
+
+    if [ -z "$CHECKSUM_PINS" ]; then
+        return 0
+    fi
+
+    if [ ! -f "$CHECKSUM_PINS" ]; then
+        error "Checksum pin file not found: $CHECKSUM_PINS"
+        return 1
+    fi
+
+    expected=$(pinned_checksum "$pin_binary" "$pin_version" "$pin_asset")
+
+    if [ -z "$expected" ]; then
+        return 0
+    fi
+
+    if [ "$expected" = "-" ]; then
+        error "$pin_binary $pin_version is pinned in $CHECKSUM_PINS, but not for $pin_asset"
+        return 1
+    fi
+
+    actual=$(sha256_of "$file") || {
+        error "sha256sum or shasum is required to verify pinned checksums"
+        return 1
+    }
+
+    if [ "$actual" != "$expected" ]; then
+        error "Checksum mismatch for $pin_binary $pin_version"
+        error "  Expected: $expected"
+        error "  Actual:   $actual"
+        return 1
+    fi
+
+    checksum_verified=true
+    info "  Checksum matches pinned value"
+}
+
+# Read the CPU architecture from an executable's ELF, Mach-O or PE header
+binary_arch() {
+    file="$1"
+
+    magic=$(od -An -tx1 -N4 "$file" 2>/dev/null | tr -d ' \n')
+    case "$magic" in
+        7f454c46)
+            # ELF: e_machine at offset 18
+            case "$(od -An -tx1 -j18 -N2 "$file" 2>/dev/null | tr -d ' \n')" in
+                3e00) echo "x86_64" ;;
+                b700) echo "aarch64" ;;
+                2800) echo "armv7" ;;
+            esac
+            ;;
+        cffaedfe)
+            # 64-bit Mach-O: cputype at offset 4
+            case "$(od -An -tx1 -j4 -N4 "$file" 2>/dev/null | tr -d ' \n')" in
+                07000001) echo "x86_64" ;;
+                0c000001) echo "aarch64" ;;
+            esac
+            ;;
+        cafebabe)
+            echo "universal"
+            ;;
+        4d5a*)
+            # PE: e_lfanew at offset 60 points at "PE\0\0" followed by the machine type
+            pe_offset=$(od -An -tu1 -j60 -N4 "$file" 2>/dev/null \
+                | awk 'NF == 4 { print $1 + $2 * 256 + $3 * 65536 + $4 * 16777216 }')
+            [ -n "$pe_offset" ] || return 0
+            case "$(od -An -tx1 -j"$pe_offset" -N6 "$file" 2>/dev/null | tr -d ' \n')" in
+                504500006486) echo "x86_64" ;;
+                5045000064aa) echo "aarch64" ;;
+                50450000c401) echo "armv7" ;;
+            esac
+            ;;
+    esac
+}
+
+# Fail if a binary was built for a different architecture than the host
+check_binary_arch() {
+    file="$1"
+    expected="$2"
+
+    actual=$(binary_arch "$file")
+    case "$actual" in
+        ""|universal|"$expected")
+            return 0
+            ;;
+    esac
+
+    error "Downloaded $actual binary on $expected host: the release asset is mislabeled"
+    return 1
+}
+
+# Check if the binary at the given path is currently executing, either
+# directly or through the bin directory link given as the second argument
+is_binary_running() {
+    target="$1"
+    link="$2"
+
+    # Linux: /proc reports the physical path of each process executable, so
+    # compare it with the target resolved the same way (e.g. through a linked HOME)
+    if [ -d /proc/self ]; then
+        resolved="$(cd -P "$(dirname "$target")" 2>/dev/null && pwd -P)/$(basename "$target")"
+        for exe in /proc/[0-9]*/exe; do
+            case "$(readlink "$exe" 2>/dev/null)" in
+                "$resolved"|"$resolved (deleted)")
+                    return 0
+                    ;;
+            esac
+        done
+        return 1
+    fi
+
+    # macOS and other platforms: lsof lists processes executing the file
+    if command -v lsof >/dev/null 2>&1; then
+        lsof -t -- "$target" >/dev/null 2>&1
+        return $?
+    fi
+
+    # Last resort: match the command line on either path
+    if command -v pgrep >/dev/null 2>&1; then
+        pgrep -f "^(${target}|${link})( |$)" >/dev/null 2>&1
+        return $?
+    fi
+
+    return 1
+}
+
+# Get the path a bin directory symlink should store for a binary; portable
+# installs use a path relative to the bin directory so the tree can be moved
+link_target() {
+    if [ "$PORTABLE" = "1" ]; then
+        echo "../${1#"${INSTALL_DIR}"/}"
+    else
+        echo "$1"
+    fi
+}
+
+# Create a bin directory entry for a binary with the given strategy
+link_binary() {
+    strategy="$1"
+    target="$2"
+    link="$3"
+
+    case "$strategy" in
+        symlink)
+            ln -s "$(link_target "$target")" "$link"
+            ;;
+        hardlink)
+            ln "$target" "$link"
+            ;;
+        copy)
+            cp "$target" "$link" && chmod +x "$link"
+            ;;
+    esac
+}
+
+# Create a bin directory entry under a temporary name, falling back through
+# the ACTIVATION strategies; prints the temporary path on success
+stage_link() {
+    name="$1"
+    target="$2"
+
+    # Filesystems without symlink support fall back to hardlinks, then copies
+    case "$ACTIVATION" in
+        auto)
+            strategies="symlink hardlink copy"
+            ;;
+        *)
+            strategies="$ACTIVATION"
+            ;;
+    esac
+
+    staged_link="${BIN_DIR}/${name}.tmp.$$"
+    for strategy in $strategies; do
+        rm -f "$staged_link"
+        if link_binary "$strategy" "$target" "$staged_link" 2>/dev/null; then
+            if [ "$strategy" != "symlink" ]; then
+                info "Activating $name using a $strategy"
+            fi
+            return 0
+        fi
+    done
+
+    rm -f "$staged_link"
+    error "Failed to activate $name"
+    return 1
+}
+
+# Keep a copy of the current bin directory entry so it can be restored
+backup_link() {
+    link_path="$1"
+    backup="$2"
+
+    rm -f "$backup"
+    if [ -L "$link_path" ]; then
+        ln -s "$(readlink "$link_path")" "$backup"
+    elif [ -e "$link_path" ]; then
+        cp -p "$link_path" "$backup"
+    fi
+}
+
+# Undo a partially applied activation, restoring the previous entries
+rollback_activation() {
+    while read -r link_path; do
+        [ -n "$link_path" ] || continue
+        if [ -e "${link_path}.bak.$$" ] || [ -L "${link_path}.bak.$$" ]; then
+            mv -f "${link_path}.bak.$$" "$link_path"
+        else
+            rm -f "$link_path"
+        fi
+    done <<EOF
+$ACTIVATED_LINKS
+EOF
+    ACTIVATED_LINKS=""
+}
+
+# Activate all queued binaries as one transaction: every entry is staged
+# first, then renamed into place, and the previous entries are restored if
+# any rename fails
+activate_all() {
+    LINK_TMPS=""
+    while read -r name target; do
+        [ -n "$name" ] || continue
+        LINK_TMPS="${LINK_TMPS}${BIN_DIR}/${name}
+"
+        if ! stage_link "$name" "$target"; then
+            cleanup_activation
+            return 1
+        fi
+        if ! backup_link "${BIN_DIR}/${name}" "${BIN_DIR}/${name}.bak.$$"; then
+            error "Failed to back up ${BIN_DIR}/${name}"
+            cleanup_activation
+            return 1
+        fi
+    done <<EOF
+$PENDING_ACTIVATIONS
+EOF
+
+    ACTIVATED_LINKS=""
+    while read -r link_path; do
+        [ -n "$link_path" ] || continue
+        ACTIVATED_LINKS="${ACTIVATED_LINKS}${link_path}
+"
+        if ! mv -f "${link_path}.tmp.$$" "$link_path"; then
+            error "Failed to activate $link_path, restoring the previous links"
+            rollback_activation
+            cleanup_activation
+            return 1
+        fi
+    done <<EOF
+$LINK_TMPS
+EOF
+
+    ACTIVATED_LINKS=""
+    cleanup_activation
+}
+
+# Remove the temporary and backup entries left by activate_all
+cleanup_activation() {
+    while read -r link_path; do
+        [ -n "$link_path" ] || continue
+        rm -f "${link_path}.tmp.$$" "${link_path}.bak.$$"
+    done <<EOF
+$LINK_TMPS
+EOF
+    LINK_TMPS=""
+}
+
+# Check if the bin directory entry already points at an installed binary
+is_activated() {
+    link_path="$1"
+    target="$2"
+
+    if [ -L "$link_path" ]; then
+        [ "$(readlink "$link_path")" = "$(link_target "$target")" ]
+    elif [ -f "$link_path" ]; then
+        cmp -s "$link_path" "$target"
+    else
+        return 1
+    fi
+}
+
+# Check if an installed binary still matches its release: the checksum
+# recorded at install time is compared with the one published now, so a
+# re-tagged release is downloaded again. Installs without a recorded checksum
+# or releases without a published one are taken as up to date, unless the
+# version is pinned in CHECKSUM_PINS, in which case the receipt must match the pin.
+is_up_to_date() {
+    binary="$1"
+    version="$2"
+    receipt="${3}.sha256"
+
+    recorded=""
+    recorded_asset=""
+    if [ -f "$receipt" ]; then
+        read -r recorded recorded_asset < "$receipt" || true
+    fi
+
+    # Pinned versions must match the pin, so without a receipt they are reinstalled
+    if [ -n "$CHECKSUM_PINS" ] && [ -f "$CHECKSUM_PINS" ] \
+        && [ -n "$(pinned_checksum "$binary" "${version#v}" "")" ]; then
+        if [ "$(pinned_checksum "$binary" "${version#v}" "$recorded_asset")" != "$recorded" ] || [ -z "$recorded" ]; then
+            warn "$binary ${version#v} does not match its pinned checksum"
+            return 1
+        fi
+    fi
+
+    if [ "$VERIFY_CHECKSUMS" = "0" ] || [ -z "$recorded" ]; then
+        return 0
+    fi
+
+    checksum_file=$(mktemp)
+    TMP_DIRS="${TMP_DIRS} ${checksum_file}"
+    expected=$(published_checksum "https://github.com/${GITHUB_ORG}/${binary}/releases/download/${version}/${recorded_asset}" "$checksum_file")
+    if [ -z "$expected" ] || [ "$expected" = "$recorded" ]; then
+        return 0
+    fi
+
+    warn "The published checksum of $recorded_asset has changed"
+    return 1
+}
+
+# Queue a binary to be activated once every binary has been installed
+queue_activation() {
+    PENDING_ACTIVATIONS="${PENDING_ACTIVATIONS}$1 $2
+"
+}
+
 # Install a single binary
 install_binary() {
     binary="$1"
     version="${2:-}"
+    asset_url=""
+    asset_sha256=""
 
     info "Installing $binary..."
 
     # Get version if not specified
     if [ -z "$version" ]; then
-        version=$(get_latest_version "$binary") || return 1
+        if [ "$PRERELEASE" = "1" ]; then
+            version=$(get_newest_release "$binary") || return 1
+        else
+            version=$(get_latest_version "$binary") || return 1
+        fi
     fi
 
     # Ensure version has 'v' prefix for URL
@@ -235,12 +1197,63 @@ install_binary() {
 
     # Create installation directory
     install_path="${VERSIONS_DIR}/${binary}/${version_display}"
+    bin_name="${binary}${EXE_SUFFIX}"
+    binary_path="${install_path}/${bin_name}"
+    bin_link="${BIN_DIR}/${bin_name}"
+
+    # Check mode: report what would change and stop before touching anything
+    if [ "$CHECK" = "1" ]; then
+        if [ ! -x "$binary_path" ] || [ "$REINSTALL" = "1" ] \
+            || ! is_up_to_date "$binary" "$version" "$binary_path"; then
+            install_status="would-install"
+        elif ! is_activated "$bin_link" "$binary_path"; then
+            install_status="would-activate"
+        else
+            install_status="unchanged"
+        fi
+        info "  Status: $install_status"
+        return 0
+    fi
+
+    # Skip the download when this version is already installed and unchanged
+    if [ -x "$binary_path" ] && [ "$REINSTALL" != "1" ]; then
+        if is_up_to_date "$binary" "$version" "$binary_path"; then
+            if ! is_activated "$bin_link" "$binary_path"; then
+                queue_activation "$bin_name" "$binary_path"
+            fi
+            install_status="unchanged"
+            success "$binary $version_display is already installed (FORCE=1 to reinstall)"
+            info "  Binary: $binary_path"
+            info "  Symlink: $bin_link"
+            return 0
+        fi
+        info "  Reinstalling $binary $version_display"
+    fi
+
+    # Refuse to overwrite a binary that is currently running
+    if [ -f "$binary_path" ] && is_binary_running "$binary_path" "$bin_link"; then
+        if [ "$FORCE" = "1" ]; then
+            warn "$binary $version_display is currently running, overwriting anyway (FORCE=1)"
+        else
+            error "$binary $version_display is currently running from $binary_path"
+            error "Stop it first, or re-run with FORCE=1 to overwrite it anyway"
+            return 1
+        fi
+    fi
+
+    # Remember directories created here so cleanup can remove them if they stay empty
+    for dir in "$install_path" "${VERSIONS_DIR}/${binary}"; do
+        if [ ! -d "$dir" ]; then
+            CREATED_DIRS="${CREATED_DIRS}${dir}
+"
+        fi
+    done
     mkdir -p "$install_path"
     mkdir -p "$BIN_DIR"
 
     # Create temp directory for download and extraction
     tmp_dir=$(mktemp -d)
-    trap "rm -rf '$tmp_dir'" EXIT
+    TMP_DIRS="${TMP_DIRS} ${tmp_dir}"
 
     # Build target strings for both formats
     os=$(detect_os)
@@ -252,15 +1265,34 @@ install_binary() {
     downloaded=false
     is_archive=false
 
+    archive_path="${tmp_dir}/download.${ext}"
+
+    # Format 0: Universal macOS archive
+    # Example: centy-daemon-v0.1.6-universal-apple-darwin.tar.gz
+    if [ "$os" = "apple-darwin" ] && [ "$MACOS_UNIVERSAL" = "1" ]; then
+        url0="https://github.com/${GITHUB_ORG}/${binary}/releases/download/${version}/${binary}-${version}-universal-${os}.${ext}"
+
+        info "  Trying: ${binary}-${version}-universal-${os}.${ext}"
+        if cached_download "$url0" "$archive_path"; then
+            downloaded=true
+            is_archive=true
+        elif [ $? -eq 2 ]; then
+            return 1
+        fi
+    fi
+
     # Format 1: New format with archive (centy-daemon style)
     # Example: centy-daemon-v0.1.6-x86_64-apple-darwin.tar.gz
-    url1="https://github.com/${GITHUB_ORG}/${binary}/releases/download/${version}/${binary}-${version}-${arch}-${os}.${ext}"
-    archive_path="${tmp_dir}/download.${ext}"
+    if [ "$downloaded" = "false" ]; then
+        url1="https://github.com/${GITHUB_ORG}/${binary}/releases/download/${version}/${binary}-${version}-${arch}-${os}.${ext}"
 
-    info "  Trying: ${binary}-${version}-${arch}-${os}.${ext}"
-    if try_download "$url1" "$archive_path"; then
-        downloaded=true
-        is_archive=true
+        info "  Trying: ${binary}-${version}-${arch}-${os}.${ext}"
+        if cached_download "$url1" "$archive_path"; then
+            downloaded=true
+            is_archive=true
+        elif [ $? -eq 2 ]; then
+            return 1
+        fi
     fi
 
     # Format 2: Legacy format raw binary (centy-tui style)
@@ -270,9 +1302,11 @@ install_binary() {
         raw_path="${tmp_dir}/${binary}"
 
         info "  Trying: ${binary}-${os_legacy}-${arch}"
-        if try_download "$url2" "$raw_path"; then
+        if cached_download "$url2" "$raw_path"; then
             downloaded=true
             is_archive=false
+        elif [ $? -eq 2 ]; then
+            return 1
         fi
     fi
 
@@ -282,9 +1316,11 @@ install_binary() {
         raw_path="${tmp_dir}/${binary}"
 
         info "  Trying: ${binary}-${os_legacy}-${arch}.exe"
-        if try_download "$url3" "$raw_path"; then
+        if cached_download "$url3" "$raw_path"; then
             downloaded=true
             is_archive=false
+        elif [ $? -eq 2 ]; then
+            return 1
         fi
     fi
 
@@ -293,6 +1329,34 @@ install_binary() {
         return 1
     fi
 
+    if [ "$is_archive" = "true" ]; then
+        downloaded_path="$archive_path"
+    else
+        downloaded_path="$raw_path"
+    fi
+
+    asset_url="$last_download_url"
+    asset_sha256=$(sha256_of "$downloaded_path") || asset_sha256=""
+
+    checksum_verified=false
+    if ! verify_published_checksum "$asset_url" "$downloaded_path" \
+        || ! verify_pinned_checksum "$binary" "$version_display" "$downloaded_path" "${asset_url##*/}"; then
+        discard_cached_download
+        return 1
+    fi
+
+    # Anyone with write access to the shared cache could have planted the
+    # file, so a cache hit is only used when a checksum vouches for it
+    if [ "$cache_hit" = "true" ] && [ "$checksum_verified" != "true" ]; then
+        info "  No checksum to verify the cached download, downloading it again"
+        if ! try_download "$asset_url" "$downloaded_path"; then
+            error "Failed to download $binary"
+            return 1
+        fi
+        asset_sha256=$(sha256_of "$downloaded_path") || asset_sha256=""
+    fi
+    store_cached_download "$downloaded_path"
+
     # Handle archive vs raw binary
     if [ "$is_archive" = "true" ]; then
         info "  Extracting..."
@@ -302,39 +1366,88 @@ install_binary() {
         fi
 
         # Find the binary in extracted contents
-        if [ -f "${tmp_dir}/${binary}" ]; then
-            mv "${tmp_dir}/${binary}" "${install_path}/${binary}"
-        else
-            found_binary=$(find "$tmp_dir" -name "$binary" -type f 2>/dev/null | head -1)
-            if [ -n "$found_binary" ]; then
-                mv "$found_binary" "${install_path}/${binary}"
-            else
-                error "Could not find $binary in extracted archive"
-                return 1
-            fi
+        staged_binary=$(find_binary_in_dir "$tmp_dir" "$binary" "$os")
+        if [ -z "$staged_binary" ]; then
+            error "Could not find $binary in extracted archive (looked for: $(binary_file_names "$binary" "$os") or $BINARY_PATTERN)"
+            return 1
         fi
     else
-        # Raw binary - just move it
-        mv "${tmp_dir}/${binary}" "${install_path}/${binary}"
+        # Raw binary - use it as downloaded
+        staged_binary="${tmp_dir}/${binary}"
     fi
 
-    binary_path="${install_path}/${binary}"
+    # Catch mislabeled assets before they fail later with "Exec format error"
+    if ! check_binary_arch "$staged_binary" "$arch"; then
+        return 1
+    fi
+
+    # Copy into the install directory under a temporary name, then rename it
+    # into place so the binary path never holds a half-written file
+    install_tmp="${binary_path}.tmp.$$"
+    mv "$staged_binary" "$install_tmp"
 
     # Make executable
-    chmod +x "$binary_path"
+    chmod +x "$install_tmp"
+    mv -f "$install_tmp" "$binary_path"
+    install_tmp=""
+
+    # Record the asset checksum so a same-version reinstall can be skipped
+    if [ -n "$asset_sha256" ]; then
+        printf '%s  %s\n' "$asset_sha256" "${asset_url##*/}" > "${binary_path}.sha256"
+    fi
 
-    # Create symlink in bin directory
-    symlink_path="${BIN_DIR}/${binary}"
-    rm -f "$symlink_path"
-    ln -s "${binary_path}" "$symlink_path"
+    queue_activation "$bin_name" "$binary_path"
 
+    install_status="installed"
     success "Installed $binary $version_display"
     info "  Binary: $binary_path"
-    info "  Symlink: $symlink_path"
+    info "  Symlink: $bin_link"
+}
+
+# Add the bin directory to the Windows user PATH so cmd and PowerShell find it
+setup_windows_path() {
+    if ! command -v powershell.exe >/dev/null 2>&1; then
+        warn "powershell.exe not found, add ${BIN_DIR} to your Windows user PATH manually"
+        return 0
+    fi
+
+    # A POSIX path in the Windows PATH would be ignored, so only go ahead
+    # when it can be converted
+    if ! command -v cygpath >/dev/null 2>&1; then
+        warn "cygpath not found, add ${BIN_DIR} to your Windows user PATH manually"
+        return 0
+    fi
+    win_bin_dir=$(cygpath -w "$BIN_DIR")
+
+    # Single quotes are doubled to escape them in a PowerShell string
+    ps_bin_dir=$(printf '%s' "$win_bin_dir" | sed "s/'/''/g")
+
+    # Edit HKCU\Environment directly so %VAR% entries stay unexpanded and the
+    # value keeps its REG_EXPAND_SZ type; setting and removing a throwaway
+    # variable through [Environment] then broadcasts WM_SETTINGCHANGE
+    if powershell.exe -NoProfile -NonInteractive -Command "
+        \$dir = '${ps_bin_dir}'
+        \$path = (Get-Item 'HKCU:\\Environment').GetValue('Path', '', 'DoNotExpandEnvironmentNames')
+        if ((\$path -split ';') -notcontains \$dir) {
+            \$entries = @(\$path.TrimEnd(';'), \$dir) | Where-Object { \$_ }
+            Set-ItemProperty -Path 'HKCU:\\Environment' -Name 'Path' -Type ExpandString -Value (\$entries -join ';')
+            [Environment]::SetEnvironmentVariable('CENTY_INSTALLER_REFRESH', '1', 'User')
+            [Environment]::SetEnvironmentVariable('CENTY_INSTALLER_REFRESH', \$null, 'User')
+        }
+    " >/dev/null 2>&1; then
+        info "Added ${win_bin_dir} to the Windows user PATH"
+    else
+        warn "Could not update the Windows user PATH, add ${win_bin_dir} manually"
+    fi
 }
 
 # Setup PATH in shell config
 setup_path() {
+    # On Windows, also register the bin directory outside of the POSIX shell
+    if [ "$(detect_os)" = "pc-windows-msvc" ]; then
+        setup_windows_path
+    fi
+
     # Check if already in PATH
     case ":$PATH:" in
         *":${BIN_DIR}:"*)
@@ -342,6 +1455,12 @@ setup_path() {
             ;;
     esac
 
+    # Without a home directory there is no shell config to update
+    if [ -z "${HOME:-}" ]; then
+        warn "HOME is not set, add ${BIN_DIR} to your PATH manually"
+        return 0
+    fi
+
     info "Adding ${BIN_DIR} to PATH..."
 
     # Detect shell and config file
@@ -383,17 +1502,74 @@ setup_path() {
     warn "Please restart your shell or run: source $config_file"
 }
 
-# Print summary
+# Print per-binary outcomes recorded by main as "binary status version" lines
+print_results() {
+    echo "Results:"
+    echo "$1" | while read -r name status ver; do
+        [ -n "$name" ] || continue
+        case "$status" in
+            installed|unchanged)
+                printf "  ${GREEN}%-14s${NC} %-20s %s\n" "$status" "$name" "$ver"
+                ;;
+            failed)
+                printf "  ${RED}%-14s${NC} %-20s %s\n" "$status" "$name" "$ver"
+                ;;
+            *)
+                printf "  ${YELLOW}%-14s${NC} %-20s %s\n" "$status" "$name" "$ver"
+                ;;
+        esac
+    done
+    echo ""
+}
+
+# Escape a string for use inside a JSON string literal
+json_escape() {
+    printf '%s' "$1" | sed 's/\\/\\\\/g; s/"/\\"/g'
+}
+
+# Write the REPORT file from "binary status version seconds url sha256" lines
+write_report() {
+    {
+        printf '{\n'
+        printf '  "platform": "%s",\n' "$(json_escape "$(detect_arch)-$(detect_os)")"
+        printf '  "install_dir": "%s",\n' "$(json_escape "$INSTALL_DIR")"
+        printf '  "binaries": [\n'
+        printf '%s\n' "$1" | sed 's/\\/\\\\/g; s/"/\\"/g' | awk '
+            NF == 0 { next }
+            {
+                if (n++) printf ",\n"
+                printf "    {\"name\": %s, \"status\": %s, \"version\": %s, \"seconds\": %s, \"url\": %s, \"sha256\": %s}", \
+                    json($1), json($2), json($3), $4, json($5), json($6)
+            }
+            END { if (n) printf "\n" }
+            function json(v) { return (v == "" || v == "-") ? "null" : "\"" v "\"" }
+        '
+        printf '  ]\n'
+        printf '}\n'
+    } > "$REPORT" || {
+        error "Failed to write report to $REPORT"
+        return 1
+    }
+    info "Report written to $REPORT"
+}
+
+# Print summary, with the binaries that failed (if any) as the argument
 print_summary() {
+    failed_binaries="$1"
+
     echo ""
     echo "============================================"
-    success "Installation complete!"
+    if [ -n "$failed_binaries" ]; then
+        warn "Installation incomplete, failed:${failed_binaries}"
+    else
+        success "Installation complete!"
+    fi
     echo "============================================"
     echo ""
     echo "Installed binaries:"
     for binary in $BINARIES; do
-        if [ -L "${BIN_DIR}/${binary}" ]; then
-            echo "  - ${BIN_DIR}/${binary}"
+        if [ -e "${BIN_DIR}/${binary}${EXE_SUFFIX}" ]; then
+            echo "  - ${BIN_DIR}/${binary}${EXE_SUFFIX}"
         fi
     done
     echo ""
@@ -402,8 +1578,55 @@ print_summary() {
     echo ""
 }
 
+# Remove temporary and partially written files from this run
+cleanup() {
+    for dir in $TMP_DIRS; do
+        rm -rf "$dir"
+    done
+    if [ -n "${cache_tmp:-}" ]; then
+        rm -f "$cache_tmp"
+    fi
+    if [ -n "${install_tmp:-}" ]; then
+        rm -f "$install_tmp"
+    fi
+    while read -r dir; do
+        [ -n "$dir" ] || continue
+        rmdir "$dir" 2>/dev/null || true
+    done <<EOF
+${CREATED_DIRS:-}
+EOF
+    if [ -n "${ACTIVATED_LINKS:-}" ]; then
+        rollback_activation
+    fi
+    if [ -n "${LINK_TMPS:-}" ]; then
+        cleanup_activation
+    fi
+}
+
+# Abort on Ctrl-C or SIGTERM, cleaning up and exiting with 128 + signal number
+on_interrupt() {
+    echo "" >&2
+    error "Installation aborted"
+    cleanup
+    exit "$1"
+}
+
 # Main function
 main() {
+    TMP_DIRS=""
+    CREATED_DIRS=""
+    PENDING_ACTIVATIONS=""
+    ACTIVATED_LINKS=""
+    LINK_TMPS=""
+    trap cleanup EXIT
+    trap 'on_interrupt 130' INT
+    trap 'on_interrupt 143' TERM
+
+    # Quiet mode: keep the original stdout on fd 3 for the final paths only
+    if [ "$QUIET" = "1" ]; then
+        exec 3>&1 >/dev/null
+    fi
+
     echo ""
     echo "============================================"
     echo "       Centy Installer"
@@ -412,44 +1635,121 @@ main() {
 
     # Check requirements
     check_requirements
+    resolve_binaries
 
     # Show configuration
+    if [ -z "${HOME:-}" ] && [ -z "${CENTY_HOME:-}" ] && [ "$INSTALL_DIR" = "$DEFAULT_INSTALL_DIR" ]; then
+        warn "HOME is not set, installing into the current directory (set CENTY_HOME or INSTALL_DIR to choose)"
+    fi
     info "Installation directory: ${INSTALL_DIR}"
+    if [ "$PORTABLE" = "1" ]; then
+        info "Portable mode: relative symlinks, shell config left untouched"
+    fi
     info "Binaries directory: ${BIN_DIR}"
+    if [ -n "$PROFILE" ]; then
+        info "Profile: ${PROFILE}"
+    fi
     info "Binaries to install: ${BINARIES}"
+    if [ -z "$VERSION" ] && [ "$PRERELEASE" = "1" ]; then
+        info "Channel: prerelease"
+    fi
     info "Platform: $(detect_arch)-$(detect_os)"
+    if [ -n "$IP_VERSION" ]; then
+        info "Address family: IPv${IP_VERSION} only"
+    fi
+    if [ -n "$RESOLVE" ]; then
+        info "DNS overrides: ${RESOLVE}"
+    fi
+    check_wsl
     echo ""
 
     # Create base directories
-    mkdir -p "${BIN_DIR}"
-    mkdir -p "${VERSIONS_DIR}"
+    if [ "$CHECK" != "1" ]; then
+        mkdir -p "${BIN_DIR}"
+        mkdir -p "${VERSIONS_DIR}"
+    fi
 
     # Install each binary
     failed=""
     installed=""
+    results=""
+    report=""
     for binary in $BINARIES; do
+        # Stop early in strict mode once something has failed
+        if [ -n "$failed" ] && [ "$CONTINUE_ON_ERROR" = "0" ]; then
+            results="${results}${binary} skipped -
+"
+            report="${report}${binary} skipped - 0 - -
+"
+            continue
+        fi
+
+        version_display=""
+        started=$(date +%s)
         if install_binary "$binary" "$VERSION"; then
             installed="${installed} ${binary}"
+            status="$install_status"
         else
             failed="${failed} ${binary}"
+            status="failed"
         fi
+        elapsed=$(($(date +%s) - started))
+        debug "  Took ${elapsed}s"
+        results="${results}${binary} ${status} ${version_display:--}
+"
+        report="${report}${binary} ${status} ${version_display:--} ${elapsed} ${asset_url:--} ${asset_sha256:--}
+"
         echo ""
     done
 
-    # Setup PATH only if something was installed
-    if [ -n "$installed" ]; then
+    # Switch the bin directory over only once every binary is in place, so
+    # the successful binaries are activated together or not at all. In
+    # strict mode a failure holds back activation entirely.
+    if [ "$CHECK" != "1" ] && [ -n "$PENDING_ACTIVATIONS" ]; then
+        activated=true
+        if [ -n "$failed" ] && [ "$CONTINUE_ON_ERROR" = "0" ]; then
+            warn "Not activating any binaries because some failed; the previous versions stay active"
+            activated=false
+        elif ! activate_all; then
+            failed="${failed}$(printf '%s\n' "$PENDING_ACTIVATIONS" | awk 'NF { sub(/\.exe$/, "", $1); printf " %s", $1 }')"
+            activated=false
+        fi
+        if [ "$activated" = "false" ]; then
+            installed=""
+            results=$(printf '%s\n' "$results" | sed 's/^\([^ ]*\) installed /\1 staged /')
+            report=$(printf '%s\n' "$report" | sed 's/^\([^ ]*\) installed /\1 staged /')
+        fi
+    fi
+
+    print_results "$results"
+
+    if [ -n "$REPORT" ]; then
+        write_report "$report" || true
+    fi
+
+    # Check mode stops after reporting
+    if [ "$CHECK" = "1" ]; then
+        if [ -n "$failed" ]; then
+            exit 1
+        fi
+        exit 0
+    fi
+
+    # Setup PATH only if something was installed; portable installs leave the profile alone
+    if [ -n "$installed" ] && [ "$PORTABLE" != "1" ]; then
         setup_path
     fi
 
+    if [ "$QUIET" = "1" ]; then
+        for binary in $installed; do
+            echo "${BIN_DIR}/${binary}${EXE_SUFFIX}" >&3
+        done
+    fi
+
     # Print summary
+    print_summary "$failed"
     if [ -n "$failed" ]; then
-        warn "Some binaries failed to install:${failed}"
-        if [ -n "$installed" ]; then
-            print_summary
-        fi
         exit 1
-    else
-        print_summary
     fi
 }
 
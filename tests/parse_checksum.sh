@@ -0,0 +1,60 @@
+#!/bin/sh
+# Tests for parse_checksum in install.sh
+#
+# Usage: sh tests/parse_checksum.sh
+
+set -e
+
+cd "$(dirname "$0")/.."
+
+# Load parse_checksum on its own so install.sh's main is not run
+eval "$(sed -n '/^parse_checksum() {$/,/^}$/p' install.sh)"
+
+HASH="0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+OTHER="fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210"
+ASSET="centy-tui-v1.0.0-x86_64-unknown-linux-gnu.tar.gz"
+
+tmp_file=$(mktemp)
+trap 'rm -f "$tmp_file"' EXIT
+
+failures=0
+
+# check <description> <expected> <checksum file contents> [asset name]
+check() {
+    printf '%s\n' "$3" > "$tmp_file"
+    actual=$(parse_checksum "$tmp_file" "${4:-$ASSET}")
+    if [ "$actual" = "$2" ]; then
+        echo "ok   - $1"
+    else
+        echo "FAIL - $1: expected '$2', got '$actual'"
+        failures=$((failures + 1))
+    fi
+}
+
+check "sha256sum text mode" "$HASH" "$HASH  $ASSET"
+check "sha256sum binary mode" "$HASH" "$HASH *$ASSET"
+check "sha256sum with ./ prefix" "$HASH" "$HASH  ./$ASSET"
+check "sha256sum with directory" "$HASH" "$HASH  dist/$ASSET"
+check "sha256sum binary mode with directory" "$HASH" "$HASH *dist/$ASSET"
+check "BSD style" "$HASH" "SHA256 ($ASSET) = $HASH"
+check "BSD style with directory" "$HASH" "SHA256 (dist/$ASSET) = $HASH"
+check "bare hash" "$HASH" "$HASH"
+check "uppercase hash" "$HASH" "$(echo "$HASH" | tr 'a-f' 'A-F')  $ASSET"
+check "CRLF line endings" "$HASH" "$(printf '%s  %s\r' "$HASH" "$ASSET")"
+check "picks the matching line" "$HASH" "$OTHER  other.tar.gz
+$HASH  $ASSET"
+
+check "no entry for the asset" "" "$OTHER  other.tar.gz"
+check "name is a suffix of another asset" "" "$OTHER  x-$ASSET"
+check "name is a prefix of another asset" "" "$OTHER  $ASSET.sig"
+check "BSD style for another asset" "" "SHA256 (other.tar.gz) = $OTHER"
+check "bare hash in a multi-line file" "" "$HASH
+$OTHER"
+check "hash of the wrong length" "" "abc123"
+check "empty file" "" ""
+
+if [ "$failures" -gt 0 ]; then
+    echo "$failures test(s) failed"
+    exit 1
+fi
+echo "All tests passed"